@@ -0,0 +1,70 @@
+//! Internal old/new alignment walk shared by [`crate::unified`] and [`crate::hunk`].
+
+use crate::{Difference, Patch};
+
+/// One step of the alignment walk between `old` and `new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    /// `old[old_index]` and `new[new_index]` are the same item in both sequences.
+    Same(usize, usize),
+    /// `old[old_index]` was removed (covers plain deletes, the old side of an update, and the old
+    /// side of a move).
+    Deleted(usize),
+    /// `new[new_index]` was added (covers plain creates, updates, and the new side of a move).
+    Inserted(usize),
+}
+
+/// Walks `old`/`new` in lockstep, consulting `diff` to decide at each step whether the next item
+/// was removed, added, or is common to both sides. A [`Patch::Move`] has no position of its own
+/// to anchor on, so its old index is treated as a delete and its new index as an insert.
+pub(crate) fn align(old_len: usize, new_len: usize, diff: &Difference) -> Vec<Step> {
+    let mut deleted = vec![false; old_len];
+    let mut moved_from = vec![false; old_len];
+    let mut created = vec![false; new_len];
+    let mut updated = vec![false; new_len];
+    let mut moved_to = vec![false; new_len];
+
+    for patch in diff {
+        match patch {
+            Patch::Delete(i) => deleted[*i] = true,
+            Patch::Create(i) => created[*i] = true,
+            Patch::Update(i) => updated[*i] = true,
+            Patch::Move(from, to) => {
+                moved_from[*from] = true;
+                moved_to[*to] = true;
+            }
+            Patch::Replace(old_range, new_range) => {
+                for i in old_range.clone() {
+                    deleted[i] = true;
+                }
+                for i in new_range.clone() {
+                    created[i] = true;
+                }
+            }
+        }
+    }
+
+    let mut steps = Vec::with_capacity(old_len + new_len);
+    let (mut o, mut n) = (0, 0);
+
+    while o < old_len || n < new_len {
+        if o < old_len && (deleted[o] || moved_from[o]) {
+            steps.push(Step::Deleted(o));
+            o += 1;
+        } else if o < old_len && n < new_len && updated[n] {
+            steps.push(Step::Deleted(o));
+            steps.push(Step::Inserted(n));
+            o += 1;
+            n += 1;
+        } else if n < new_len && (created[n] || moved_to[n] || updated[n]) {
+            steps.push(Step::Inserted(n));
+            n += 1;
+        } else {
+            steps.push(Step::Same(o, n));
+            o += 1;
+            n += 1;
+        }
+    }
+
+    steps
+}
@@ -0,0 +1,212 @@
+//! Post-processing a computed [`Difference`] into coalesced [`Patch::Replace`] blocks.
+
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::{diff, Difference, Patch};
+
+/// Computes the difference between `old` and `new`, then compacts it: a [`Patch::Delete`] run
+/// and [`Patch::Create`] run occupying the same gap in the old/new alignment are merged into a
+/// single [`Patch::Replace`], and each resulting block is then slid forward as far as it can go
+/// while still representing the same change, so it lands on a natural boundary (e.g. a whole
+/// added paragraph rather than half of it plus a shared blank line).
+pub fn diff_compact<T: Eq + Hash>(old: &[T], new: &[T]) -> Difference {
+    compact(old, new, &diff(old, new))
+}
+
+// One step of the old/new walk `compact` groups by, built the same way `align` walks the
+// alignment, but keeping `Delete`/`Create` distinct from `Update`/`Move` so only genuine
+// delete-then-create gaps are candidates for merging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Same,
+    Deleted(usize),
+    Inserted(usize),
+    Updated(usize),
+    Moved(usize, usize),
+}
+
+// Walks `old`/`new` in lockstep like `align` does, but reports plain deletes/creates separately
+// from updates and moves, since only the former may be coalesced into a `Replace`.
+fn walk(old_len: usize, new_len: usize, diff: &Difference) -> Vec<Step> {
+    let mut deleted = vec![false; old_len];
+    let mut moved_from = vec![false; old_len];
+    let mut move_to = vec![None; old_len];
+    let mut created = vec![false; new_len];
+    let mut updated = vec![false; new_len];
+    let mut moved_to = vec![false; new_len];
+
+    for patch in diff {
+        match patch {
+            Patch::Delete(i) => deleted[*i] = true,
+            Patch::Create(i) => created[*i] = true,
+            Patch::Update(i) => updated[*i] = true,
+            Patch::Move(from, to) => {
+                moved_from[*from] = true;
+                moved_to[*to] = true;
+                move_to[*from] = Some(*to);
+            }
+            Patch::Replace(..) => unreachable!("compact runs on a pre-compaction diff, which never contains Replace"),
+        }
+    }
+
+    let mut steps = Vec::with_capacity(old_len + new_len);
+    let (mut o, mut n) = (0, 0);
+
+    while o < old_len || n < new_len {
+        if o < old_len && (deleted[o] || moved_from[o]) {
+            if deleted[o] {
+                steps.push(Step::Deleted(o));
+            } else {
+                steps.push(Step::Moved(o, move_to[o].expect("moved_from implies a recorded destination")));
+            }
+            o += 1;
+        } else if n < new_len && updated[n] {
+            steps.push(Step::Updated(n));
+            o += 1;
+            n += 1;
+        } else if n < new_len && (created[n] || moved_to[n]) {
+            // A move's arrival was already emitted as `Step::Moved` at its departure index, so
+            // only a genuine create is reported here; the move's new slot is just a non-mergeable
+            // `Same`-like gap as far as delete/create coalescing is concerned.
+            steps.push(if created[n] { Step::Inserted(n) } else { Step::Same });
+            n += 1;
+        } else {
+            steps.push(Step::Same);
+            o += 1;
+            n += 1;
+        }
+    }
+
+    steps
+}
+
+fn compact<T: PartialEq>(old: &[T], new: &[T], diff: &Difference) -> Difference {
+    let steps = walk(old.len(), new.len(), diff);
+    let mut result = Difference::new();
+    let mut i = 0;
+
+    while i < steps.len() {
+        match steps[i] {
+            Step::Same => i += 1,
+            Step::Updated(n) => {
+                result.push(Patch::Update(n));
+                i += 1;
+            }
+            Step::Moved(from, to) => {
+                result.push(Patch::Move(from, to));
+                i += 1;
+            }
+            Step::Inserted(start) => {
+                let mut end = start + 1;
+                let mut j = i + 1;
+                while let Some(Step::Inserted(c)) = steps.get(j) {
+                    if *c != end {
+                        break;
+                    }
+                    end += 1;
+                    j += 1;
+                }
+
+                for c in start..end {
+                    result.push(Patch::Create(c));
+                }
+                i = j;
+            }
+            Step::Deleted(start) => {
+                let mut old_end = start + 1;
+                let mut j = i + 1;
+                while let Some(Step::Deleted(d)) = steps.get(j) {
+                    if *d != old_end {
+                        break;
+                    }
+                    old_end += 1;
+                    j += 1;
+                }
+
+                let mut new_range: Option<Range<usize>> = None;
+                let mut k = j;
+                while let Some(Step::Inserted(c)) = steps.get(k) {
+                    match &mut new_range {
+                        Some(r) if r.end == *c => r.end = *c + 1,
+                        Some(_) => break,
+                        None => new_range = Some(*c..*c + 1),
+                    }
+                    k += 1;
+                }
+
+                match new_range {
+                    Some(new_range) => {
+                        let (old_range, new_range) = slide(old, new, start..old_end, new_range);
+                        result.push(Patch::Replace(old_range, new_range));
+                        i = k;
+                    }
+                    None => {
+                        for d in start..old_end {
+                            result.push(Patch::Delete(d));
+                        }
+                        i = j;
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// Shifts a delete/create block forward as far as it can go while still deleting and inserting
+// the same content: a shift is valid only when the item just past the block equals the item
+// leaving it at the front, on both sides at once.
+fn slide<T: PartialEq>(
+    old: &[T],
+    new: &[T],
+    mut o: Range<usize>,
+    mut n: Range<usize>,
+) -> (Range<usize>, Range<usize>) {
+    while o.end < old.len() && n.end < new.len() && old[o.start] == old[o.end] && new[n.start] == new[n.end] {
+        o = o.start + 1..o.end + 1;
+        n = n.start + 1..n.end + 1;
+    }
+
+    (o, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_delete_create_pair_into_replace() {
+        let old = vec!["a", "b", "c"];
+        let new = vec!["x", "y", "c"];
+
+        assert_eq!(vec![Patch::Replace(0..2, 0..2)], diff_compact(&old, &new));
+    }
+
+    #[test]
+    fn pairs_deletes_and_creates_by_aligned_position_not_vec_order() {
+        // "b" and "c" are shared, so the algorithm's raw diff front-loads both deletes before
+        // both creates: Delete(0), Delete(3), Create(0), Create(3). Adjacency-in-the-`Vec`
+        // compaction would wrongly pair Delete(3) with Create(0) into Replace(3..4, 0..1); the
+        // aligned gaps are actually 0..1/0..1 ("a" -> "x") and 3..4/3..4 ("d" -> "y").
+        let old = vec!["a", "b", "c", "d"];
+        let new = vec!["x", "b", "c", "y"];
+
+        assert_eq!(
+            vec![Patch::Replace(0..1, 0..1), Patch::Replace(3..4, 3..4)],
+            diff_compact(&old, &new)
+        );
+    }
+
+    #[test]
+    fn slides_block_forward_onto_a_natural_boundary() {
+        // Both items right after the block match the block's own first item, so it can shift
+        // forward by one without changing what it represents.
+        let old = vec!["x", "x", "b"];
+        let new = vec!["y", "y", "z"];
+
+        assert_eq!((1..2, 1..2), slide(&old, &new, 0..1, 0..1));
+    }
+
+}
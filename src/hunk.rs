@@ -0,0 +1,134 @@
+//! Grouping a [`Difference`] into contiguous [`Hunk`]s, coalescing changes that sit close
+//! together.
+
+use std::ops::Range;
+
+use crate::align::{align, Step};
+use crate::{Difference, Patch};
+
+/// A contiguous run of patches plus the unchanged items between them, with the old- and new-side
+/// index ranges it spans so callers can slice `old`/`new` for rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    pub old_range: Range<usize>,
+    pub new_range: Range<usize>,
+    pub patches: Vec<Patch>,
+}
+
+/// Groups `diff` into [`Hunk`]s: two changed regions end up in the same hunk whenever fewer than
+/// `max_distance` unchanged items separate them, otherwise they start a new hunk.
+pub fn group_into_hunks(diff: &Difference, max_distance: usize) -> Vec<Hunk> {
+    let old_len = diff
+        .iter()
+        .map(|p| match p {
+            Patch::Delete(i) | Patch::Move(i, _) => *i + 1,
+            Patch::Replace(old_range, _) => old_range.end,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0);
+    let new_len = diff
+        .iter()
+        .map(|p| match p {
+            Patch::Create(i) | Patch::Update(i) | Patch::Move(_, i) => *i + 1,
+            Patch::Replace(_, new_range) => new_range.end,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0);
+
+    let steps = align(old_len, new_len, diff);
+
+    let changed = steps
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| !matches!(s, Step::Same(..)))
+        .map(|(i, _)| i);
+
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for i in changed {
+        match spans.last_mut() {
+            Some(last) if i - last.1 - 1 < max_distance => last.1 = i,
+            _ => spans.push((i, i)),
+        }
+    }
+
+    spans
+        .into_iter()
+        .map(|(start, end)| build_hunk(&steps, start, end, diff))
+        .collect()
+}
+
+fn build_hunk(steps: &[Step], start: usize, end: usize, diff: &Difference) -> Hunk {
+    let span = &steps[start..=end];
+
+    let old_range = bounds(span.iter().filter_map(|s| match *s {
+        Step::Same(o, _) | Step::Deleted(o) => Some(o),
+        Step::Inserted(_) => None,
+    }));
+    let new_range = bounds(span.iter().filter_map(|s| match *s {
+        Step::Same(_, n) | Step::Inserted(n) => Some(n),
+        Step::Deleted(_) => None,
+    }));
+
+    let patches = diff
+        .iter()
+        .filter(|p| match p {
+            Patch::Delete(i) => old_range.contains(i),
+            Patch::Create(i) | Patch::Update(i) => new_range.contains(i),
+            Patch::Move(from, to) => old_range.contains(from) || new_range.contains(to),
+            Patch::Replace(r1, r2) => ranges_overlap(r1, &old_range) || ranges_overlap(r2, &new_range),
+        })
+        .cloned()
+        .collect();
+
+    Hunk {
+        old_range,
+        new_range,
+        patches,
+    }
+}
+
+fn ranges_overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+fn bounds(indices: impl Iterator<Item = usize>) -> Range<usize> {
+    match indices.fold(None, |acc: Option<(usize, usize)>, i| match acc {
+        Some((min, max)) => Some((min.min(i), max.max(i))),
+        None => Some((i, i)),
+    }) {
+        Some((min, max)) => min..max + 1,
+        None => 0..0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+
+    #[test]
+    fn merges_nearby_changes() {
+        let old = vec!["a", "b", "c", "d", "e"];
+        let new = vec!["x", "b", "c", "y", "e"];
+        let d = diff(&old, &new);
+
+        let hunks = group_into_hunks(&d, 3);
+
+        assert_eq!(1, hunks.len());
+        assert_eq!(0..4, hunks[0].old_range);
+        assert_eq!(0..4, hunks[0].new_range);
+    }
+
+    #[test]
+    fn splits_far_apart_changes() {
+        let old = vec!["a", "b", "c", "d", "e", "f", "g"];
+        let new = vec!["x", "b", "c", "d", "e", "f", "y"];
+        let d = diff(&old, &new);
+
+        let hunks = group_into_hunks(&d, 1);
+
+        assert_eq!(2, hunks.len());
+    }
+}
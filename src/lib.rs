@@ -5,8 +5,21 @@
 use std::collections::HashMap;
 use std::vec::Vec;
 use std::hash::Hash;
+use std::ops::Range;
+
+mod align;
+mod compact;
+mod hunk;
+mod refine;
+mod text;
+mod unified;
 
 pub use self::Patch::*;
+pub use compact::diff_compact;
+pub use hunk::{group_into_hunks, Hunk};
+pub use refine::{diff_with_refinement, Refine, RefinedPatch};
+pub use text::{diff_lines, diff_words, tokenize_lines, tokenize_words};
+pub use unified::to_unified;
 
 /// Finds difference between two slices of objects using Paul Heckel's algorithm.
 ///
@@ -34,6 +47,35 @@ pub use self::Patch::*;
 /// assert_eq!(answer, diff(&old, &new));
 /// ```
 pub fn diff<T: Eq + Hash>(old: &[T], new: &[T]) -> Difference {
+    let mut collector = Collector::default();
+    diff_hook(old, new, &mut collector);
+    collector.result
+}
+
+/// Streaming visitor for a diff, driven by the same algorithm as [`diff`] but without
+/// materializing a [`Difference`]. Runs of adjacent equal, deleted, or inserted items are
+/// collapsed into single calls carrying a `len`, rather than one call per index, so callers
+/// diffing large inputs can stream straight into a renderer, a patch applier, or a serializer
+/// instead of paying for an intermediate `Vec`.
+pub trait DiffHook {
+    /// `len` consecutive items starting at `old_index`/`new_index` are unchanged.
+    fn equal(&mut self, old_index: usize, new_index: usize, len: usize);
+    /// `len` consecutive items starting at `old_index` were removed; `new_index` is where they
+    /// would have sat in `new`.
+    fn delete(&mut self, old_index: usize, len: usize, new_index: usize);
+    /// `len` consecutive items starting at `new_index` were added; `old_index` is where they
+    /// would have sat in `old`.
+    fn insert(&mut self, old_index: usize, new_index: usize, len: usize);
+    /// The matched item at `new_index` (whose old counterpart is `old_index`) differs in
+    /// content from that counterpart.
+    fn update(&mut self, old_index: usize, new_index: usize);
+    /// The matched item moved from `from` to `to`.
+    fn moved(&mut self, from: usize, to: usize);
+}
+
+/// Computes the difference between `old` and `new`, dispatching each change to `hook` instead
+/// of collecting it into a [`Difference`].
+pub fn diff_hook<T: Eq + Hash, H: DiffHook>(old: &[T], new: &[T], hook: &mut H) {
     let mut table: Table<&T> = HashMap::new();
     let mut na: Array = vec![None; new.len()];
     let mut oa: Array = vec![None; old.len()];
@@ -43,19 +85,55 @@ pub fn diff<T: Eq + Hash>(old: &[T], new: &[T]) -> Difference {
     pass3(new, table, &mut oa, &mut na);
     pass4(old, new, &mut oa, &mut na);
     pass5(old, new, &mut oa, &mut na);
-    pass6(old, new, oa, na)
+    pass6(old, new, oa, na, hook);
+}
+
+// The thin hook that backs `diff`, expanding batched callbacks back into one `Patch` per index
+// so the resulting `Difference` is unchanged from before `diff_hook` existed.
+#[derive(Default)]
+struct Collector {
+    result: Difference,
+}
+
+impl DiffHook for Collector {
+    fn equal(&mut self, _old_index: usize, _new_index: usize, _len: usize) {}
+
+    fn delete(&mut self, old_index: usize, len: usize, _new_index: usize) {
+        for i in old_index..old_index + len {
+            self.result.push(Patch::Delete(i));
+        }
+    }
+
+    fn insert(&mut self, _old_index: usize, new_index: usize, len: usize) {
+        for i in new_index..new_index + len {
+            self.result.push(Patch::Create(i));
+        }
+    }
+
+    fn update(&mut self, old_index: usize, new_index: usize) {
+        let _ = old_index;
+        self.result.push(Patch::Update(new_index));
+    }
+
+    fn moved(&mut self, from: usize, to: usize) {
+        self.result.push(Patch::Move(from, to));
+    }
 }
 
 /// Contains patches between two slices of objects.
 pub type Difference = Vec<Patch>;
 
 /// Represents patch between two slices of objects.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Patch {
     Create(usize),
     Update(usize),
     Move(usize, usize),
     Delete(usize),
+    /// `old_range` was replaced by `new_range` as a single block. Only ever produced by
+    /// [`diff_compact`], which coalesces adjacent [`Delete`](Patch::Delete)/[`Create`](Patch::Create)
+    /// runs that occupy the same gap.
+    Replace(Range<usize>, Range<usize>),
 }
 
 type Table<T> = HashMap<T, TableEntry>;
@@ -198,46 +276,134 @@ fn pass5<T: Eq>(old: &[T], new: &[T], oa: &mut Array, na: &mut Array) {
 // file is output with its changes described in a form
 // appropriate to a particular application environment.
 //
-fn pass6<T: Eq>(old: &[T], new: &[T], oa: Array, na: Array) -> Difference {
-    let mut result = Difference::new();
+fn pass6<T: Eq, H: DiffHook>(old: &[T], new: &[T], oa: Array, na: Array, hook: &mut H) {
     let mut delete_offsets = Vec::with_capacity(oa.len());
     let mut offset = 0;
 
-    for (i, x) in oa.iter().enumerate() {
+    for x in oa.iter() {
         delete_offsets.push(offset);
         if x.is_none() {
-            result.push(Patch::Delete(i));
             offset += 1;
         }
     }
 
+    let mut i = 0;
+    while i < oa.len() {
+        if oa[i].is_none() {
+            let start = i;
+            while i < oa.len() && oa[i].is_none() {
+                i += 1;
+            }
+            let new_index = if start == 0 { 0 } else { oa[start - 1].map_or(0, |n| n + 1) };
+            hook.delete(start, i - start, new_index);
+        } else {
+            i += 1;
+        }
+    }
+
     offset = 0;
+    let mut next_old = 0;
+    let mut i = 0;
 
-    for (i, x) in na.into_iter().enumerate() {
-        match x {
+    while i < na.len() {
+        match na[i] {
             Some(j) => {
-                if old[j] != new[i] {
-                    result.push(Patch::Update(i));
+                next_old = j + 1;
+                let is_update = old[j] != new[i];
+                let is_moved = j + offset - delete_offsets[j] != i;
+
+                if is_update {
+                    hook.update(j, i);
                 }
 
-                if j + offset - delete_offsets[j] != i {
-                    result.push(Patch::Move(j, i));
+                if is_moved {
+                    hook.moved(j, i);
+                    i += 1;
+                } else if is_update {
+                    i += 1;
+                } else {
+                    let start = i;
+                    let start_old = j;
+                    i += 1;
+                    while i < na.len() {
+                        match na[i] {
+                            Some(j2) if j2 == next_old && old[j2] == new[i] && j2 + offset - delete_offsets[j2] == i => {
+                                next_old = j2 + 1;
+                                i += 1;
+                            }
+                            _ => break,
+                        }
+                    }
+                    hook.equal(start_old, start, i - start);
                 }
             }
             None => {
-                result.push(Patch::Create(i));
-                offset += 1;
+                let start = i;
+                while i < na.len() && na[i].is_none() {
+                    offset += 1;
+                    i += 1;
+                }
+                hook.insert(next_old, start, i - start);
             }
         }
     }
-
-    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[derive(Default)]
+    struct Recorder {
+        deletes: Vec<(usize, usize, usize)>,
+        inserts: Vec<(usize, usize)>,
+        equals: Vec<(usize, usize, usize)>,
+    }
+
+    impl DiffHook for Recorder {
+        fn equal(&mut self, old_index: usize, new_index: usize, len: usize) {
+            self.equals.push((old_index, new_index, len));
+        }
+
+        fn delete(&mut self, old_index: usize, len: usize, new_index: usize) {
+            self.deletes.push((old_index, len, new_index));
+        }
+
+        fn insert(&mut self, _old_index: usize, new_index: usize, len: usize) {
+            self.inserts.push((new_index, len));
+        }
+
+        fn update(&mut self, _old_index: usize, _new_index: usize) {}
+
+        fn moved(&mut self, _from: usize, _to: usize) {}
+    }
+
+    #[test]
+    fn diff_hook_batches_adjacent_runs() {
+        let old = vec!["a", "b", "c", "d"];
+        let new = vec!["x", "y", "c", "d"];
+
+        let mut recorder = Recorder::default();
+        diff_hook(&old, &new, &mut recorder);
+
+        assert_eq!(vec![(0, 2, 0)], recorder.deletes);
+        assert_eq!(vec![(0, 2)], recorder.inserts);
+        assert_eq!(vec![(2, 2, 2)], recorder.equals);
+    }
+
+    #[test]
+    fn delete_reports_its_aligned_new_index() {
+        // "a" and "b" are deleted from between "k" and nothing; the block sits at new index 1,
+        // right after "k", not at index 0 (the count of preceding deletions).
+        let old = vec!["k", "a", "b"];
+        let new = vec!["k", "x"];
+
+        let mut recorder = Recorder::default();
+        diff_hook(&old, &new, &mut recorder);
+
+        assert_eq!(vec![(1, 2, 1)], recorder.deletes);
+    }
+
     #[test]
     fn no_changes() {
         let old = vec!["a"];
@@ -0,0 +1,113 @@
+//! Second-level diffing of coalesced [`Patch::Replace`] pairs, so callers see the minimal
+//! differing spans inside a changed item instead of a whole-item delete+insert.
+
+use std::hash::Hash;
+
+use crate::text::tokenize_words;
+use crate::{diff, diff_compact, Difference, Patch};
+
+/// A [`Patch`](crate::Patch)-like result where aligned-but-differing positions carry a nested
+/// diff of their own refined contents instead of a plain delete+insert pair.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefinedPatch {
+    Create(usize),
+    Update(usize, Difference),
+    Move(usize, usize),
+    Delete(usize),
+}
+
+/// Converts a value into the finer-grained token sequence used to refine an aligned
+/// delete/insert pair. The built-in text front end implements this for `&str` by splitting it
+/// into words.
+pub trait Refine {
+    type Token: Eq + Hash;
+
+    fn refine(&self) -> Vec<Self::Token>;
+}
+
+impl Refine for &str {
+    type Token = String;
+
+    fn refine(&self) -> Vec<String> {
+        tokenize_words(self).into_iter().map(|r| self[r].to_string()).collect()
+    }
+}
+
+/// Diffs `old` and `new` via [`diff_compact`], then re-diffs the refined token sequence of each
+/// equal-length [`Patch::Replace`] pair position-by-position via [`Refine`], so e.g. a changed
+/// line surfaces as the minimal differing word spans instead of a full line delete+insert.
+///
+/// [`diff`] itself only ever matches equal positions, so it never produces a [`Patch::Update`]
+/// to refine directly; `diff_compact`'s aligned [`Patch::Replace`] blocks are what actually carry
+/// a changed line, which is why refinement is built on top of it. A `Replace` whose sides differ
+/// in length refines only their common prefix; the remainder is left as plain deletes/creates,
+/// since there is no 1-to-1 position left to refine against.
+pub fn diff_with_refinement<T>(old: &[T], new: &[T]) -> Vec<RefinedPatch>
+where
+    T: Eq + Hash + Refine,
+{
+    diff_compact(old, new)
+        .into_iter()
+        .flat_map(|patch| refine(old, new, patch))
+        .collect()
+}
+
+fn refine<T: Refine>(old: &[T], new: &[T], patch: Patch) -> Vec<RefinedPatch> {
+    match patch {
+        Patch::Create(i) => vec![RefinedPatch::Create(i)],
+        Patch::Delete(i) => vec![RefinedPatch::Delete(i)],
+        Patch::Move(from, to) => vec![RefinedPatch::Move(from, to)],
+        Patch::Update(_) => {
+            unreachable!("diff_compact never emits Update: diff's matching only links equal positions")
+        }
+        Patch::Replace(old_range, new_range) => {
+            let common = old_range.len().min(new_range.len());
+            let mut patches = Vec::new();
+
+            for k in 0..common {
+                let old_index = old_range.start + k;
+                let new_index = new_range.start + k;
+                let sub = diff(&old[old_index].refine(), &new[new_index].refine());
+                patches.push(RefinedPatch::Update(new_index, sub));
+            }
+            for old_index in old_range.start + common..old_range.end {
+                patches.push(RefinedPatch::Delete(old_index));
+            }
+            for new_index in new_range.start + common..new_range.end {
+                patches.push(RefinedPatch::Create(new_index));
+            }
+
+            patches
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refines_an_update_into_word_level_spans() {
+        let old = vec!["I did not have sexual relations"];
+        let new = vec!["I may have had sexual relations"];
+
+        match &diff_with_refinement(&old, &new)[..] {
+            [RefinedPatch::Update(0, sub)] => {
+                assert!(!sub.is_empty());
+                assert!(sub.iter().any(|p| matches!(p, Patch::Create(_))));
+            }
+            other => panic!("expected a single refined update, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn replace_with_mismatched_lengths_refines_only_the_common_prefix() {
+        let old = vec!["the quick fox", "jumps"];
+        let new = vec!["the slow fox"];
+
+        assert_eq!(
+            vec![RefinedPatch::Update(0, diff(&"the quick fox".refine(), &"the slow fox".refine())), RefinedPatch::Delete(1)],
+            diff_with_refinement(&old, &new)
+        );
+    }
+}
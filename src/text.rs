@@ -0,0 +1,112 @@
+//! Convenience front end for diffing text, split into lines or words.
+
+use std::ops::Range;
+
+use crate::{diff, Difference};
+
+/// Diffs `old` and `new` line by line. Each line's range runs up to and including its trailing
+/// `\n`, except for a final unterminated line.
+pub fn diff_lines(old: &str, new: &str) -> Difference {
+    let old_lines: Vec<&str> = tokenize_lines(old).into_iter().map(|r| &old[r]).collect();
+    let new_lines: Vec<&str> = tokenize_lines(new).into_iter().map(|r| &new[r]).collect();
+
+    diff(&old_lines, &new_lines)
+}
+
+/// Diffs `old` and `new` word by word, where a "word" is a maximal run of `[A-Za-z0-9_]` bytes
+/// or a maximal run of anything else, so punctuation and whitespace become their own tokens.
+pub fn diff_words(old: &str, new: &str) -> Difference {
+    let old_words: Vec<&str> = tokenize_words(old).into_iter().map(|r| &old[r]).collect();
+    let new_words: Vec<&str> = tokenize_words(new).into_iter().map(|r| &new[r]).collect();
+
+    diff(&old_words, &new_words)
+}
+
+/// Splits `text` into line byte ranges by scanning for `\n`, with a trailing range for any
+/// final unterminated line.
+pub fn tokenize_lines(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'\n' {
+            ranges.push(start..i + 1);
+            start = i + 1;
+        }
+    }
+    if start < bytes.len() {
+        ranges.push(start..bytes.len());
+    }
+
+    ranges
+}
+
+/// Splits `text` into word byte ranges, cutting a boundary wherever a `[A-Za-z0-9_]` byte
+/// borders a byte outside that class.
+pub fn tokenize_words(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    for i in 1..bytes.len() {
+        if is_word_byte(bytes[i]) != is_word_byte(bytes[i - 1]) {
+            ranges.push(start..i);
+            start = i;
+        }
+    }
+    if start < bytes.len() {
+        ranges.push(start..bytes.len());
+    }
+
+    ranges
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Patch::*;
+
+    #[test]
+    fn tokenize_lines_with_trailing_newline() {
+        let ranges = tokenize_lines("foo\nbar\n");
+
+        assert_eq!(vec!["foo\n", "bar\n"], ranges.into_iter().map(|r| &"foo\nbar\n"[r]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn tokenize_lines_without_trailing_newline() {
+        let ranges = tokenize_lines("foo\nbar");
+
+        assert_eq!(vec!["foo\n", "bar"], ranges.into_iter().map(|r| &"foo\nbar"[r]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn tokenize_words_groups_non_word_bytes() {
+        let ranges = tokenize_words("a, b!");
+
+        // ", " is a single run of non-word bytes, so it stays one token.
+        assert_eq!(
+            vec!["a", ", ", "b", "!"],
+            ranges.into_iter().map(|r| &"a, b!"[r]).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn diff_lines_reports_changed_line() {
+        let d = diff_lines("a\nb\nc\n", "a\nx\nc\n");
+
+        assert!(d.contains(&Delete(1)) || d.iter().any(|p| matches!(p, Create(_))));
+    }
+
+    #[test]
+    fn diff_words_reports_changed_word() {
+        let d = diff_words("much writing", "much typing");
+
+        assert!(d.iter().any(|p| matches!(p, Create(_))));
+    }
+}
@@ -0,0 +1,172 @@
+//! Rendering a computed [`Difference`] as standard unified-diff text.
+
+use std::fmt::Display;
+use std::ops::Range;
+
+use crate::align::{align, Step as Line};
+use crate::Difference;
+
+/// Renders the [`Difference`] between `old` and `new` as unified-diff text, with `context`
+/// unchanged lines of padding kept around each hunk.
+///
+/// A [`Patch::Move`](crate::Patch::Move) has no equivalent in the unified-diff format, so it is
+/// rendered as a delete from `old` paired with an insert into `new`.
+pub fn to_unified<T: Display>(old: &[T], new: &[T], diff: &Difference, context: usize) -> String {
+    let lines = align(old.len(), new.len(), diff);
+    let hunks = group(&lines, context);
+
+    let mut out = String::new();
+    for hunk in hunks {
+        render_hunk(&mut out, old, new, &lines, hunk);
+    }
+    out
+}
+
+// Groups the indices of `lines` into ranges covering each hunk: a run of changed lines padded
+// with up to `context` unchanged lines on either side, merging any hunks whose padding overlaps
+// or touches.
+fn group(lines: &[Line], context: usize) -> Vec<Range<usize>> {
+    let mut hunks: Vec<Range<usize>> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if matches!(line, Line::Same(..)) {
+            continue;
+        }
+
+        let start = i.saturating_sub(context);
+        let end = (i + 1 + context).min(lines.len());
+
+        match hunks.last_mut() {
+            Some(prev) if start <= prev.end => prev.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+
+    hunks
+}
+
+fn render_hunk<T: Display>(out: &mut String, old: &[T], new: &[T], lines: &[Line], hunk: Range<usize>) {
+    let body = &lines[hunk.clone()];
+
+    let old_indices: Vec<usize> = body
+        .iter()
+        .filter_map(|l| match *l {
+            Line::Same(o, _) | Line::Deleted(o) => Some(o),
+            Line::Inserted(_) => None,
+        })
+        .collect();
+    let new_indices: Vec<usize> = body
+        .iter()
+        .filter_map(|l| match *l {
+            Line::Same(_, n) | Line::Inserted(n) => Some(n),
+            Line::Deleted(_) => None,
+        })
+        .collect();
+
+    let preceding_old = preceding(lines, hunk.start, |l| match *l {
+        Line::Same(o, _) | Line::Deleted(o) => Some(o),
+        Line::Inserted(_) => None,
+    });
+    let preceding_new = preceding(lines, hunk.start, |l| match *l {
+        Line::Same(_, n) | Line::Inserted(n) => Some(n),
+        Line::Deleted(_) => None,
+    });
+
+    let (old_start, old_len) = header(&old_indices, preceding_old);
+    let (new_start, new_len) = header(&new_indices, preceding_new);
+
+    out.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_len, new_start, new_len));
+
+    for line in body {
+        match *line {
+            Line::Same(o, _) => out.push_str(&format!(" {}\n", old[o])),
+            Line::Deleted(o) => out.push_str(&format!("-{}\n", old[o])),
+            Line::Inserted(n) => out.push_str(&format!("+{}\n", new[n])),
+        }
+    }
+}
+
+// Scans `lines[..before]` in reverse for the last index `pick` resolves on one side, so an empty
+// side can anchor its header to the line that precedes it instead of claiming index `0`.
+fn preceding(lines: &[Line], before: usize, pick: impl Fn(&Line) -> Option<usize>) -> Option<usize> {
+    lines[..before].iter().rev().find_map(pick)
+}
+
+// Converts a sorted list of touched indices into a 1-based `(start, len)` hunk header pair. An
+// empty side anchors to the line preceding the hunk (`(p + 1, 0)`), falling back to `(0, 0)`
+// only when the hunk sits at the very start of that side, matching GNU/git convention.
+fn header(indices: &[usize], preceding: Option<usize>) -> (usize, usize) {
+    match (indices.first(), indices.last()) {
+        (Some(&first), Some(&last)) => (first + 1, last - first + 1),
+        _ => match preceding {
+            Some(p) => (p + 1, 0),
+            None => (0, 0),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diff, Patch};
+
+    #[test]
+    fn no_changes() {
+        let old = vec!["a", "b", "c"];
+        let new = vec!["a", "b", "c"];
+        let d = diff(&old, &new);
+
+        assert_eq!("", to_unified(&old, &new, &d, 3));
+    }
+
+    #[test]
+    fn simple_update() {
+        let old = vec!["a", "b", "c"];
+        let new = vec!["a", "x", "c"];
+        let d = vec![Patch::Update(1)];
+
+        assert_eq!(
+            "@@ -1,3 +1,3 @@\n a\n-b\n+x\n c\n",
+            to_unified(&old, &new, &d, 1)
+        );
+    }
+
+    #[test]
+    fn insert_with_no_context() {
+        let old = vec!["a", "c"];
+        let new = vec!["a", "b", "c"];
+        let d = diff(&old, &new);
+
+        assert_eq!("@@ -1,0 +2,1 @@\n+b\n", to_unified(&old, &new, &d, 0));
+    }
+
+    #[test]
+    fn insert_at_start_of_file_with_no_context() {
+        let old = vec!["a", "b"];
+        let new = vec!["x", "a", "b"];
+        let d = diff(&old, &new);
+
+        assert_eq!("@@ -0,0 +1,1 @@\n+x\n", to_unified(&old, &new, &d, 0));
+    }
+
+    #[test]
+    fn update_with_no_matching_old_position_degrades_to_an_insert_instead_of_panicking() {
+        // A hand-built `Patch::Update` whose index has no remaining old counterpart (here, `old`
+        // is exhausted after index 0) used to make `align` push `Step::Deleted` past the end of
+        // `old`, which this then indexed out of bounds.
+        let old = vec!["a"];
+        let new = vec!["a", "b"];
+        let d = vec![Patch::Update(1)];
+
+        assert_eq!("@@ -1,0 +2,1 @@\n+b\n", to_unified(&old, &new, &d, 0));
+    }
+
+    #[test]
+    fn delete_with_no_context() {
+        let old = vec!["a", "b", "c"];
+        let new = vec!["a", "c"];
+        let d = diff(&old, &new);
+
+        assert_eq!("@@ -2,1 +1,0 @@\n-b\n", to_unified(&old, &new, &d, 0));
+    }
+}